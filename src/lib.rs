@@ -44,13 +44,47 @@
 //!        );
 //! ```
 
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::iter::{Fuse, FusedIterator};
+
+/// The folding decision made by a [`CoalesceBy`] core for a single `(last, item)` pair.
+///
+/// Returning `Ok(merged)` folds `item` into the run, replacing the held last item with `merged`.
+/// Returning `Err((emit, new_last))` refuses the fold: `emit` is yielded now and `new_last` becomes
+/// the held last item for the next run.
+pub trait CoalescePredicate<Item, T> {
+    fn coalesce_pair(&mut self, last: T, item: Item) -> Result<T, (T, T)>;
+}
+
+/// A generic core that collapses adjacent items according to a [`CoalescePredicate`], holding the
+/// current last item by value. The dedup adapters are thin wrappers over this core, which lets them
+/// share one implementation and avoid cloning elements.
+pub struct CoalesceBy<I, F, T> {
+    iter: Fuse<I>,
+    last: Option<T>,
+    f: F,
+}
+
+/// Coalesce predicate for `dedup`: keeps the held item when it equals the next one.
+pub struct DedupPred;
+
+/// Coalesce predicate for `dedup_by`: keeps the held item when `same_bucket` returns `true`.
+pub struct DedupByPred<F>(F);
+
+/// Coalesce predicate for `dedup_by_key`: keeps the held item when both items map to the same key.
+/// The key of the held representative is cached in `last_key` so it is computed only once per run.
+pub struct DedupByKeyPred<F, K> {
+    key: F,
+    last_key: Option<K>,
+}
+
 /// An iterator that removes elements that are the same as previous one.
 ///
 /// This struct is created by the `dedup` method of trait `DedupAdapter`, implemented on Iterator.
 /// To use the `dedup` method, `use dedup_iter::DedupAdapter`.
 pub struct Dedup<I, T> {
-    iter: I,
-    current_item: Option<T>,
+    inner: CoalesceBy<I, DedupPred, T>,
 }
 
 /// An iterator that removes elements that are the same as previous one, according the provided function.
@@ -58,9 +92,7 @@ pub struct Dedup<I, T> {
 /// This struct is created by the `dedup_by` method of trait `DedupByAdapter`, implemented on Iterator.
 /// To use the `dedup_by` method, `use dedup_iter::DedupByAdapter`.
 pub struct DedupBy<I, F, T> {
-    iter: I,
-    current_item: Option<T>,
-    same_bucket: F,
+    inner: CoalesceBy<I, DedupByPred<F>, T>,
 }
 
 /// An iterator that removes elements that have a key that is the same as the key of previous element.
@@ -68,24 +100,381 @@ pub struct DedupBy<I, F, T> {
 ///
 /// This struct is created by the `dedup_by_key` method of trait `DedupByKeyAdapter`, implemented on Iterator.
 /// To use the `dedup_by_key` method, `use dedup_iter::DedupByKeyAdapter`.
-pub struct DedupByKey<I, F, K> {
+pub struct DedupByKey<I, F, K>
+where
+    I: Iterator,
+{
+    inner: CoalesceBy<I, DedupByKeyPred<F, K>, I::Item>,
+}
+
+/// An iterator that collapses consecutive equal elements and yields `(count, representative)` pairs,
+/// where `count` is the number of elements in the run and `representative` is its first element.
+///
+/// This struct is created by the `dedup_with_count` method of trait `DedupAdapter`, implemented on Iterator.
+/// To use the `dedup_with_count` method, `use dedup_iter::DedupAdapter`.
+pub struct DedupWithCount<I, T> {
+    iter: I,
+    current_item: Option<T>,
+}
+
+/// An iterator that collapses consecutive equal elements, according to the provided function,
+/// and yields `(count, representative)` pairs where `representative` is the first element of the run.
+///
+/// This struct is created by the `dedup_by_with_count` method of trait `DedupByAdapter`, implemented on Iterator.
+/// To use the `dedup_by_with_count` method, `use dedup_iter::DedupByAdapter`.
+pub struct DedupByWithCount<I, F, T> {
+    iter: I,
+    current_item: Option<T>,
+    same_bucket: F,
+}
+
+/// An iterator that collapses consecutive elements that share a key and yields `(count, representative)` pairs,
+/// where `representative` is the first element of the run. The client provided function computes the key.
+///
+/// This struct is created by the `dedup_by_key_with_count` method of trait `DedupByKeyAdapter`, implemented on Iterator.
+/// To use the `dedup_by_key_with_count` method, `use dedup_iter::DedupByKeyAdapter`.
+pub struct DedupByKeyWithCount<I, F, K, T> {
     iter: I,
-    current_key: Option<K>,
+    current: Option<(K, T)>,
     key: F,
 }
 
+impl<T> CoalescePredicate<T, T> for DedupPred
+where
+    T: PartialEq,
+{
+    fn coalesce_pair(&mut self, last: T, item: T) -> Result<T, (T, T)> {
+        if last == item {
+            Ok(last)
+        } else {
+            Err((last, item))
+        }
+    }
+}
+
+impl<F, T> CoalescePredicate<T, T> for DedupByPred<F>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    fn coalesce_pair(&mut self, last: T, item: T) -> Result<T, (T, T)> {
+        if (self.0)(&last, &item) {
+            Ok(last)
+        } else {
+            Err((last, item))
+        }
+    }
+}
+
+impl<F, K, T> CoalescePredicate<T, T> for DedupByKeyPred<F, K>
+where
+    F: FnMut(&T) -> K,
+    K: PartialEq,
+{
+    fn coalesce_pair(&mut self, last: T, item: T) -> Result<T, (T, T)> {
+        let last_key = match self.last_key.take() {
+            Some(last_key) => last_key,
+            None => (self.key)(&last),
+        };
+        let item_key = (self.key)(&item);
+        if last_key == item_key {
+            self.last_key = Some(last_key);
+            Ok(last)
+        } else {
+            self.last_key = Some(item_key);
+            Err((last, item))
+        }
+    }
+}
+
+impl<I, F, T> Iterator for CoalesceBy<I, F, T>
+where
+    I: Iterator<Item = T>,
+    F: CoalescePredicate<T, T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let mut last = match self.last.take() {
+            Some(last) => last,
+            None => self.iter.next()?,
+        };
+        for item in self.iter.by_ref() {
+            match self.f.coalesce_pair(last, item) {
+                Ok(merged) => last = merged,
+                Err((emit, new_last)) => {
+                    self.last = Some(new_last);
+                    return Some(emit);
+                }
+            }
+        }
+        Some(last)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let buffered = self.last.is_some() as usize;
+        (buffered.min(lower), upper.map(|u| u + buffered))
+    }
+
+    fn fold<Acc, G>(mut self, mut acc: Acc, mut g: G) -> Acc
+    where
+        G: FnMut(Acc, T) -> Acc,
+    {
+        let mut last = match self.last.take() {
+            Some(last) => last,
+            None => match self.iter.next() {
+                Some(x) => x,
+                None => return acc,
+            },
+        };
+        for item in self.iter {
+            match self.f.coalesce_pair(last, item) {
+                Ok(merged) => last = merged,
+                Err((emit, new_last)) => {
+                    acc = g(acc, emit);
+                    last = new_last;
+                }
+            }
+        }
+        g(acc, last)
+    }
+}
+
+impl<I, F, T> FusedIterator for CoalesceBy<I, F, T>
+where
+    I: Iterator<Item = T>,
+    F: CoalescePredicate<T, T>,
+{
+}
+
 impl<I, T> Iterator for Dedup<I, T>
 where
     I: Iterator<Item = T>,
-    T: PartialEq + Clone,
+    T: PartialEq,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn fold<Acc, G>(self, acc: Acc, g: G) -> Acc
+    where
+        G: FnMut(Acc, Self::Item) -> Acc,
+    {
+        self.inner.fold(acc, g)
+    }
+}
+
+impl<I, T> FusedIterator for Dedup<I, T>
+where
+    I: Iterator<Item = T>,
+    T: PartialEq,
+{
+}
+
+impl<I, F, T> Iterator for DedupBy<I, F, T>
+where
+    I: Iterator<Item = T>,
+    F: FnMut(&T, &T) -> bool,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn fold<Acc, G>(self, acc: Acc, g: G) -> Acc
+    where
+        G: FnMut(Acc, Self::Item) -> Acc,
+    {
+        self.inner.fold(acc, g)
+    }
+}
+
+impl<I, F, T> FusedIterator for DedupBy<I, F, T>
+where
+    I: Iterator<Item = T>,
+    F: FnMut(&T, &T) -> bool,
+{
+}
+
+impl<I, F, K> Iterator for DedupByKey<I, F, K>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn fold<Acc, G>(self, acc: Acc, g: G) -> Acc
+    where
+        G: FnMut(Acc, Self::Item) -> Acc,
+    {
+        self.inner.fold(acc, g)
+    }
+}
+
+impl<I, F, K> FusedIterator for DedupByKey<I, F, K>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+}
+
+/// An iterator that yields only the first occurrence of each distinct element, removing *all*
+/// later duplicates regardless of position. Unlike the dedup family it does not require the
+/// source to be sorted; seen elements are remembered in a `HashSet`.
+///
+/// This struct is created by the `unique` method of trait `UniqueAdapter`, implemented on Iterator.
+/// To use the `unique` method, `use dedup_iter::UniqueAdapter`.
+pub struct Unique<I, T> {
+    iter: I,
+    seen: HashSet<T>,
+}
+
+/// An iterator that yields only the first element for each distinct key, removing *all* later
+/// elements whose key has been seen before. Only the key is stored, so the element itself need
+/// not be hashable or cloneable.
+///
+/// This struct is created by the `unique_by` method of trait `UniqueByAdapter`, implemented on Iterator.
+/// To use the `unique_by` method, `use dedup_iter::UniqueByAdapter`.
+pub struct UniqueBy<I, F, K> {
+    iter: I,
+    key: F,
+    seen: HashSet<K>,
+}
+
+impl<I, T> Iterator for DedupWithCount<I, T>
+where
+    I: Iterator<Item = T>,
+    T: PartialEq,
+{
+    type Item = (usize, T);
+
+    fn next(&mut self) -> Option<(usize, T)> {
+        let representative = match self.current_item.take() {
+            Some(representative) => representative,
+            None => self.iter.next()?,
+        };
+        let mut count = 1;
+        for x in self.iter.by_ref() {
+            if x == representative {
+                count += 1;
+            } else {
+                self.current_item = Some(x);
+                return Some((count, representative));
+            }
+        }
+        Some((count, representative))
+    }
+}
+
+impl<I, F, T> Iterator for DedupByWithCount<I, F, T>
+where
+    I: Iterator<Item = T>,
+    F: FnMut(&T, &T) -> bool,
+{
+    type Item = (usize, T);
+
+    fn next(&mut self) -> Option<(usize, T)> {
+        let representative = match self.current_item.take() {
+            Some(representative) => representative,
+            None => self.iter.next()?,
+        };
+        let mut count = 1;
+        for x in self.iter.by_ref() {
+            if (self.same_bucket)(&representative, &x) {
+                count += 1;
+            } else {
+                self.current_item = Some(x);
+                return Some((count, representative));
+            }
+        }
+        Some((count, representative))
+    }
+}
+
+impl<I, F, K, T> Iterator for DedupByKeyWithCount<I, F, K, T>
+where
+    I: Iterator<Item = T>,
+    F: FnMut(&T) -> K,
+    K: PartialEq,
+{
+    type Item = (usize, T);
+
+    fn next(&mut self) -> Option<(usize, T)> {
+        let (key, representative) = match self.current.take() {
+            Some(current) => current,
+            None => {
+                let x = self.iter.next()?;
+                ((self.key)(&x), x)
+            }
+        };
+        let mut count = 1;
+        for x in self.iter.by_ref() {
+            let x_key = (self.key)(&x);
+            if x_key == key {
+                count += 1;
+            } else {
+                self.current = Some((x_key, x));
+                return Some((count, representative));
+            }
+        }
+        Some((count, representative))
+    }
+}
+
+/// An iterator that yields each element that occurs more than once in the source, emitting it a
+/// single time at its second occurrence. It is the inverse of `Unique`; seen elements are tracked
+/// in a `HashMap` that also records whether each has already been emitted as a duplicate.
+///
+/// This struct is created by the `duplicates` method of trait `DuplicatesAdapter`, implemented on Iterator.
+/// To use the `duplicates` method, `use dedup_iter::DuplicatesAdapter`.
+pub struct Duplicates<I, T> {
+    iter: I,
+    seen: HashMap<T, bool>,
+}
+
+/// An iterator that yields each element whose key occurs more than once in the source, emitting it
+/// a single time at the second occurrence of that key. Only the key is stored, so the element
+/// itself need not be hashable. The client provided function computes the key.
+///
+/// This struct is created by the `duplicates_by_key` method of trait `DuplicatesByKeyAdapter`, implemented on Iterator.
+/// To use the `duplicates_by_key` method, `use dedup_iter::DuplicatesByKeyAdapter`.
+pub struct DuplicatesBy<I, F, K> {
+    iter: I,
+    key: F,
+    seen: HashMap<K, bool>,
+}
+
+impl<I, T> Iterator for Unique<I, T>
+where
+    I: Iterator<Item = T>,
+    T: Eq + Hash + Clone,
 {
     type Item = I::Item;
 
     fn next(&mut self) -> Option<I::Item> {
         for x in self.iter.by_ref() {
-            let item = Some(x.clone());
-            if self.current_item != item {
-                self.current_item = item;
+            if self.seen.insert(x.clone()) {
                 return Some(x);
             }
         }
@@ -93,23 +482,17 @@ where
     }
 }
 
-impl<I, F, T> Iterator for DedupBy<I, F, T>
+impl<I, F, K> Iterator for UniqueBy<I, F, K>
 where
-    I: Iterator<Item = T>,
-    T: Clone,
-    F: Fn(&T, &T) -> bool,
+    I: Iterator,
+    F: Fn(&I::Item) -> K,
+    K: Eq + Hash,
 {
     type Item = I::Item;
 
     fn next(&mut self) -> Option<I::Item> {
         for x in self.iter.by_ref() {
-            let item = Some(x.clone());
-            let different = match self.current_item {
-                None => true,
-                Some(ref current_item) => !(self.same_bucket)(current_item, &x), 
-            };
-            if different {
-                self.current_item = item;
+            if self.seen.insert((self.key)(&x)) {
                 return Some(x);
             }
         }
@@ -117,24 +500,52 @@ where
     }
 }
 
-impl<I, F, K> Iterator for DedupByKey<I, F, K>
+impl<I, T> Iterator for Duplicates<I, T>
+where
+    I: Iterator<Item = T>,
+    T: Eq + Hash + Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        for x in self.iter.by_ref() {
+            match self.seen.get_mut(&x) {
+                None => {
+                    self.seen.insert(x.clone(), false);
+                }
+                Some(emitted) => {
+                    if !*emitted {
+                        *emitted = true;
+                        return Some(x);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<I, F, K> Iterator for DuplicatesBy<I, F, K>
 where
     I: Iterator,
     F: Fn(&I::Item) -> K,
-    K: PartialEq,
+    K: Eq + Hash,
 {
     type Item = I::Item;
 
     fn next(&mut self) -> Option<I::Item> {
         for x in self.iter.by_ref() {
             let key = (self.key)(&x);
-            let different = match self.current_key {
-                None => true,
-                Some(ref current_key) => key != *current_key,
-            };
-            if different {
-                self.current_key = Some(key);
-                return Some(x);
+            match self.seen.get_mut(&key) {
+                None => {
+                    self.seen.insert(key, false);
+                }
+                Some(emitted) => {
+                    if !*emitted {
+                        *emitted = true;
+                        return Some(x);
+                    }
+                }
             }
         }
         None
@@ -148,6 +559,19 @@ pub trait DedupAdapter: Iterator {
         Self: Sized,
     {
         Dedup {
+            inner: CoalesceBy {
+                iter: self.fuse(),
+                last: None,
+                f: DedupPred,
+            },
+        }
+    }
+
+    fn dedup_with_count(self) -> DedupWithCount<Self, Self::Item>
+    where
+        Self: Sized,
+    {
+        DedupWithCount {
             iter: self,
             current_item: None,
         }
@@ -159,9 +583,24 @@ pub trait DedupByAdapter<F>: Iterator {
     fn dedup_by(self, same_bucket: F) -> DedupBy<Self, F, Self::Item>
     where
         Self: Sized,
-        F: Fn(&Self::Item, &Self::Item) -> bool,
+        F: FnMut(&Self::Item, &Self::Item) -> bool,
     {
         DedupBy {
+            inner: CoalesceBy {
+                iter: self.fuse(),
+                last: None,
+                f: DedupByPred(same_bucket),
+            },
+        }
+
+    }
+
+    fn dedup_by_with_count(self, same_bucket: F) -> DedupByWithCount<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item, &Self::Item) -> bool,
+    {
+        DedupByWithCount {
             iter: self,
             current_item: None,
             same_bucket: same_bucket,
@@ -175,11 +614,29 @@ pub trait DedupByKeyAdapter<F, K>: Iterator {
     fn dedup_by_key(self, key: F) -> DedupByKey<Self, F, K>
     where
         Self: Sized,
-        F: Fn(&Self::Item) -> K,
+        F: FnMut(&Self::Item) -> K,
     {
         DedupByKey {
+            inner: CoalesceBy {
+                iter: self.fuse(),
+                last: None,
+                f: DedupByKeyPred {
+                    key: key,
+                    last_key: None,
+                },
+            },
+        }
+
+    }
+
+    fn dedup_by_key_with_count(self, key: F) -> DedupByKeyWithCount<Self, F, K, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> K,
+    {
+        DedupByKeyWithCount {
             iter: self,
-            current_key: None,
+            current: None,
             key: key,
         }
 
@@ -204,6 +661,90 @@ where
 {
 }
 
+/// Provides the `unique` method on `Iterator`s.
+pub trait UniqueAdapter: Iterator {
+    fn unique(self) -> Unique<Self, Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Eq + Hash + Clone,
+    {
+        Unique {
+            iter: self,
+            seen: HashSet::new(),
+        }
+    }
+}
+
+/// Provides the `unique_by` method on `Iterator`s.
+pub trait UniqueByAdapter<F, K>: Iterator {
+    fn unique_by(self, key: F) -> UniqueBy<Self, F, K>
+    where
+        Self: Sized,
+        F: Fn(&Self::Item) -> K,
+        K: Eq + Hash,
+    {
+        UniqueBy {
+            iter: self,
+            key: key,
+            seen: HashSet::new(),
+        }
+    }
+}
+
+impl<I> UniqueAdapter for I
+where
+    I: Iterator,
+{
+}
+
+impl<I, F, K> UniqueByAdapter<F, K> for I
+where
+    I: Iterator,
+{
+}
+
+/// Provides the `duplicates` method on `Iterator`s.
+pub trait DuplicatesAdapter: Iterator {
+    fn duplicates(self) -> Duplicates<Self, Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Eq + Hash + Clone,
+    {
+        Duplicates {
+            iter: self,
+            seen: HashMap::new(),
+        }
+    }
+}
+
+/// Provides the `duplicates_by_key` method on `Iterator`s.
+pub trait DuplicatesByKeyAdapter<F, K>: Iterator {
+    fn duplicates_by_key(self, key: F) -> DuplicatesBy<Self, F, K>
+    where
+        Self: Sized,
+        F: Fn(&Self::Item) -> K,
+        K: Eq + Hash,
+    {
+        DuplicatesBy {
+            iter: self,
+            key: key,
+            seen: HashMap::new(),
+        }
+    }
+}
+
+impl<I> DuplicatesAdapter for I
+where
+    I: Iterator,
+{
+}
+
+impl<I, F, K> DuplicatesByKeyAdapter<F, K> for I
+where
+    I: Iterator,
+{
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -298,4 +839,168 @@ mod tests {
         let v = t.chars().dedup_by_key(|_| 0).collect::<String>();
         assert_eq!(&v, "a");
     }
+
+    #[test]
+    fn dedup_with_count_runs() {
+        let t = "aaabbc";
+        let v = t.chars().dedup_with_count().collect::<Vec<_>>();
+        assert_eq!(v, vec![(3, 'a'), (2, 'b'), (1, 'c')]);
+    }
+
+    #[test]
+    fn dedup_with_count_empty() {
+        let t = Vec::<u8>::new();
+        let c = t.into_iter().dedup_with_count().count();
+        assert_eq!(c, 0);
+    }
+
+    #[test]
+    fn dedup_by_with_count_whitespace() {
+        let t = "a  b   c";
+        let v = t.chars()
+            .dedup_by_with_count(|a, b| a.is_whitespace() && b.is_whitespace())
+            .collect::<Vec<_>>();
+        assert_eq!(v, vec![(1, 'a'), (2, ' '), (1, 'b'), (3, ' '), (1, 'c')]);
+    }
+
+    #[test]
+    fn unique_keeps_first_occurrence() {
+        let t = "abacabad";
+        let v = t.chars().unique().collect::<String>();
+        assert_eq!(&v, "abcd");
+    }
+
+    #[test]
+    fn unique_empty() {
+        let t = Vec::<u8>::new();
+        let c = t.into_iter().unique().count();
+        assert_eq!(c, 0);
+    }
+
+    #[test]
+    fn unique_by_first_per_key() {
+        let t = vec![10, 11, 20, 12, 21];
+        let v = t.into_iter().unique_by(|n| n / 10).collect::<Vec<_>>();
+        assert_eq!(v, vec![10, 20]);
+    }
+
+    #[test]
+    fn unique_by_case_insensitive() {
+        let t = "First In, Last Out";
+        let v = t.chars()
+            .unique_by(|c| c.to_ascii_lowercase())
+            .collect::<String>();
+        assert_eq!(&v, "First n,LaOu");
+    }
+
+    #[test]
+    fn dedup_by_key_calls_key_once_per_element() {
+        let t = vec![1, 1, 1, 2, 2, 3];
+        let mut calls = 0;
+        let v = t.iter()
+            .dedup_by_key(|n| {
+                calls += 1;
+                **n
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(v, vec![&1, &2, &3]);
+        assert_eq!(calls, t.len());
+    }
+
+    #[test]
+    fn dedup_size_hint() {
+        let t = vec![1, 1, 2, 3, 3];
+        let it = t.iter().dedup();
+        assert_eq!(it.size_hint(), (0, Some(5)));
+    }
+
+    #[test]
+    fn dedup_size_hint_counts_buffered_item() {
+        let mut it = vec![1, 2].into_iter().dedup();
+        assert_eq!(it.next(), Some(1));
+        // Source is now exhausted but `2` is still buffered; the upper bound must not drop below it.
+        let (_, upper) = it.size_hint();
+        assert_eq!(upper, Some(1));
+        assert_eq!(it.collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn dedup_fold_matches_next() {
+        let t = vec![1, 1, 2, 3, 3, 3, 2];
+        let sum = t.iter().dedup().fold(0, |acc, &x| acc + x);
+        assert_eq!(sum, 1 + 2 + 3 + 2);
+    }
+
+    #[test]
+    fn dedup_by_key_with_rolling_counter() {
+        // A key function that maintains a rolling counter; correct only when `key` is invoked
+        // exactly once per element, in order (requires both `FnMut` and the cached-key fix).
+        let mut i = 0;
+        let v = "abcdef".chars()
+            .dedup_by_key(|_| {
+                let bucket = i / 2;
+                i += 1;
+                bucket
+            })
+            .collect::<String>();
+        assert_eq!(&v, "ace");
+    }
+
+    #[test]
+    fn dedup_by_with_stateful_closure() {
+        // A predicate that mutates captured state on each call, only expressible with `FnMut`.
+        let mut calls = 0;
+        let v = "aabbc".chars()
+            .dedup_by(|a, b| {
+                calls += 1;
+                a == b
+            })
+            .collect::<String>();
+        assert_eq!(&v, "abc");
+        assert!(calls > 0);
+    }
+
+    #[test]
+    fn dedup_without_clone() {
+        let t = vec![
+            String::from("a"),
+            String::from("a"),
+            String::from("b"),
+            String::from("a"),
+        ];
+        let v = t.into_iter().dedup().collect::<Vec<_>>();
+        assert_eq!(v, vec!["a", "b", "a"]);
+    }
+
+    #[test]
+    fn duplicates_at_second_occurrence() {
+        let t = "abacabad";
+        let v = t.chars().duplicates().collect::<String>();
+        assert_eq!(&v, "ab");
+    }
+
+    #[test]
+    fn duplicates_none_when_all_unique() {
+        let t = "abcd";
+        let c = t.chars().duplicates().count();
+        assert_eq!(c, 0);
+    }
+
+    #[test]
+    fn duplicates_by_key_emits_once() {
+        let t = vec![10, 20, 11, 30, 21, 12];
+        let v = t.into_iter()
+            .duplicates_by_key(|n| n / 10)
+            .collect::<Vec<_>>();
+        assert_eq!(v, vec![11, 21]);
+    }
+
+    #[test]
+    fn dedup_by_key_with_count_keeps_first() {
+        let t = vec![10, 11, 20, 30, 31];
+        let v = t.into_iter()
+            .dedup_by_key_with_count(|n| n / 10)
+            .collect::<Vec<_>>();
+        assert_eq!(v, vec![(2, 10), (1, 20), (2, 30)]);
+    }
 }